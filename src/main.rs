@@ -14,6 +14,7 @@ use std::path::{
 };
 
 use anyhow::{
+    bail,
     Context,
     Result,
 };
@@ -27,6 +28,7 @@ use toml_edit::{
     InlineTable,
     Item,
     Table,
+    TableLike,
     Value,
 };
 use walkdir::WalkDir;
@@ -65,6 +67,11 @@ struct FmtArgs {
     /// Suppress output when there are no changes
     #[arg(long)]
     quiet: bool,
+
+    /// Keep full version requirements (e.g. `1.0.0`) instead of trimming
+    /// redundant trailing zero components (e.g. `1.0.0` -> `1`)
+    #[arg(long)]
+    keep_full_version: bool,
 }
 
 fn main() -> Result<()> {
@@ -147,33 +154,150 @@ impl Logger {
     }
 }
 
-fn fmt_toml(args: FmtArgs) -> Result<()> {
-    let mut logger = Logger::new(args.quiet);
+/// User-overridable formatting config, loaded from `fmt-toml.toml` at the
+/// workspace root. Any key left out of the file falls back to its default.
+#[derive(Debug, Clone)]
+struct FmtConfig {
+    /// Desired top-level section order (see `reorder_sections`)
+    section_order: Vec<String>,
+    /// Desired `[package]` key order (see `format_package_section`)
+    package_key_order: Vec<String>,
+    /// Glob-like roots to scan for member `Cargo.toml` files (see
+    /// `discover_crate_manifests`)
+    scan_roots: Vec<String>,
+}
+
+impl Default for FmtConfig {
+    fn default() -> Self {
+        Self {
+            section_order: [
+                "package",
+                "lib",
+                "bin",
+                "test",
+                "bench",
+                "example",
+                "dependencies",
+                "dev-dependencies",
+                "build-dependencies",
+                "target",
+                "features",
+            ]
+            .map(String::from)
+            .to_vec(),
+            package_key_order: [
+                "name",
+                "description",
+                "version",
+                "edition",
+                "license-file",
+                "authors",
+                "rust-version",
+                "readme",
+            ]
+            .map(String::from)
+            .to_vec(),
+            scan_roots: vec!["crates/*".to_string()],
+        }
+    }
+}
+
+impl FmtConfig {
+    /// Loads `fmt-toml.toml` from `workspace_path`, falling back to defaults
+    /// for any key that file doesn't set (or if the file doesn't exist).
+    fn load(workspace_path: &Path) -> Result<Self> {
+        let config_path = workspace_path.join("fmt-toml.toml");
+        let defaults = Self::default();
+
+        if !config_path.exists() {
+            return Ok(defaults);
+        }
+
+        let content = std::fs::read_to_string(&config_path)
+            .context(format!("Failed to read {:?}", config_path))?;
+        let doc = content
+            .parse::<DocumentMut>()
+            .context(format!("Failed to parse {:?}", config_path))?;
+
+        let string_array = |key: &str| -> Option<Vec<String>> {
+            doc.get(key).and_then(|item| item.as_array()).map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+        };
 
-    let crates_dir = args.workspace_path.join("crates");
-    let mut crate_manifests = Vec::new();
+        Ok(Self {
+            section_order: string_array("section_order").unwrap_or(defaults.section_order),
+            package_key_order: string_array("package_key_order")
+                .unwrap_or(defaults.package_key_order),
+            scan_roots: string_array("scan_roots").unwrap_or(defaults.scan_roots),
+        })
+    }
+}
 
-    for entry in WalkDir::new(&crates_dir)
-        .min_depth(2)
-        .max_depth(2)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.file_name() == Some("Cargo.toml".as_ref()) {
-            crate_manifests.push(path.to_path_buf());
+/// Discovers member `Cargo.toml` manifests under the configured scan roots.
+///
+/// Each root supports three forms, relative to `workspace_path`:
+/// - `dir/*` - Cargo.toml files directly inside immediate subdirectories of
+///   `dir` (the historical `crates/*` behavior)
+/// - `dir/**` - Cargo.toml files anywhere beneath `dir`, at any depth
+/// - `dir` - a single manifest at `dir/Cargo.toml`, for nested workspaces
+fn discover_crate_manifests(workspace_path: &Path, scan_roots: &[String]) -> Vec<PathBuf> {
+    let mut manifests = Vec::new();
+
+    for root in scan_roots {
+        if let Some(prefix) = root.strip_suffix("/**") {
+            let base = workspace_path.join(prefix);
+            manifests.extend(
+                WalkDir::new(&base)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().file_name() == Some("Cargo.toml".as_ref()))
+                    .map(|e| e.path().to_path_buf()),
+            );
+        } else if let Some(prefix) = root.strip_suffix("/*") {
+            let base = workspace_path.join(prefix);
+            manifests.extend(
+                WalkDir::new(&base)
+                    .min_depth(2)
+                    .max_depth(2)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().file_name() == Some("Cargo.toml".as_ref()))
+                    .map(|e| e.path().to_path_buf()),
+            );
+        } else {
+            let candidate = workspace_path.join(root).join("Cargo.toml");
+            if candidate.is_file() {
+                manifests.push(candidate);
+            }
         }
     }
 
+    manifests.sort();
+    manifests.dedup();
+    manifests
+}
+
+fn fmt_toml(args: FmtArgs) -> Result<()> {
+    let mut logger = Logger::new(args.quiet);
+
+    let config = FmtConfig::load(&args.workspace_path)?;
+    let crate_manifests = discover_crate_manifests(&args.workspace_path, &config.scan_roots);
+
     let mut total_changes = 0;
     let mut files_changed = 0;
 
+    let hoisted = hoist_workspace_dependencies(&args, &crate_manifests, &mut logger)?;
+    total_changes += hoisted;
+
     logger.set_progress(crate_manifests.len() as u64);
     logger.set_message("🔍 Formatting Cargo.toml files");
 
     for manifest_path in &crate_manifests {
         logger.inc();
-        let changes = format_manifest(manifest_path, &args, &mut logger)?;
+        let changes = format_manifest(manifest_path, &args, &config, &mut logger)?;
         if changes > 0 {
             total_changes += changes;
             files_changed += 1;
@@ -208,7 +332,268 @@ fn fmt_toml(args: FmtArgs) -> Result<()> {
     Ok(())
 }
 
-fn format_manifest(manifest_path: &Path, args: &FmtArgs, logger: &mut Logger) -> Result<usize> {
+/// Hoist dependency versions shared across member manifests into
+/// `[workspace.dependencies]` in the workspace-root `Cargo.toml`, and rewrite
+/// each member entry to `{ workspace = true }`.
+///
+/// Member-local `features`, `optional`, and `default-features` keys are kept
+/// on the member entry alongside `workspace = true`, matching how cargo
+/// itself allows those keys to be overridden per-member. Dependencies that
+/// carry `path`, `git`, or `registry` are left untouched entirely (see
+/// `has_external_source`). If two members request semver-conflicting
+/// versions for the same dependency, this returns an error rather than
+/// silently picking one.
+fn hoist_workspace_dependencies(
+    args: &FmtArgs,
+    crate_manifests: &[PathBuf],
+    logger: &mut Logger,
+) -> Result<usize> {
+    let root_manifest_path = args.workspace_path.join("Cargo.toml");
+    if !root_manifest_path.exists() {
+        return Ok(0);
+    }
+
+    let root_content = std::fs::read_to_string(&root_manifest_path)
+        .context(format!("Failed to read {:?}", root_manifest_path))?;
+    let mut root_doc = root_content
+        .parse::<DocumentMut>()
+        .context(format!("Failed to parse {:?}", root_manifest_path))?;
+
+    let mut member_docs = Vec::new();
+    for manifest_path in crate_manifests {
+        let content = std::fs::read_to_string(manifest_path)
+            .context(format!("Failed to read {:?}", manifest_path))?;
+        let doc = content
+            .parse::<DocumentMut>()
+            .context(format!("Failed to parse {:?}", manifest_path))?;
+        member_docs.push((manifest_path.clone(), doc));
+    }
+
+    // Collect a canonical version requirement per dependency name, erroring
+    // out if two members disagree.
+    let mut collected: BTreeMap<String, (Value, PathBuf)> = BTreeMap::new();
+    for (manifest_path, doc) in &member_docs {
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            let Some(deps) = doc.get(section).and_then(|d| d.as_table_like()) else {
+                continue;
+            };
+            for (name, item) in deps.iter() {
+                if is_workspace_dependency(item) || has_external_source(item) {
+                    continue;
+                }
+                let Some(version) = dependency_version_value(item) else {
+                    continue;
+                };
+
+                match collected.get(name) {
+                    Some((existing, existing_path)) => {
+                        let existing_str = dependency_version_str(existing).unwrap_or_default();
+                        let new_str = dependency_version_str(&version).unwrap_or_default();
+                        // Compare normalized requirements rather than raw
+                        // strings, so e.g. "1.0" and "1.0.0" (the same
+                        // requirement written two ways) aren't flagged as a
+                        // conflict.
+                        if normalize_version_requirement(&existing_str, false)
+                            != normalize_version_requirement(&new_str, false)
+                        {
+                            bail!(
+                                "Conflicting version requirements for dependency `{}`: {:?} \
+                                 requests \"{}\" but {:?} requests \"{}\"",
+                                name,
+                                existing_path,
+                                existing_str,
+                                manifest_path,
+                                new_str,
+                            );
+                        }
+                    }
+                    None => {
+                        collected.insert(name.to_string(), (version, manifest_path.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    if collected.is_empty() {
+        return Ok(0);
+    }
+
+    if root_doc.get("workspace").is_none() {
+        root_doc.insert("workspace", Item::Table(Table::new()));
+    }
+    let workspace_table = root_doc
+        .get_mut("workspace")
+        .and_then(|w| w.as_table_mut())
+        .context("`workspace` section is not a table")?;
+
+    if workspace_table.get("dependencies").is_none() {
+        workspace_table.insert("dependencies", Item::Table(Table::new()));
+    }
+    let ws_deps = workspace_table
+        .get_mut("dependencies")
+        .and_then(|d| d.as_table_mut())
+        .context("`workspace.dependencies` section is not a table")?;
+
+    let mut changes = 0;
+    for (name, (version, _)) in &collected {
+        if upsert_workspace_dependency_version(ws_deps, name, version) {
+            changes += 1;
+        }
+    }
+
+    // `workspace.dependencies` never goes through `format_manifest` (the root
+    // manifest isn't part of `crate_manifests`), so run it through the same
+    // sort/normalize passes member dependency tables get, here.
+    changes += sort_table_in_place(ws_deps, logger)?;
+    changes += normalize_dependency_table(ws_deps, args.keep_full_version);
+
+    for (manifest_path, mut doc) in member_docs {
+        let mut member_changed = false;
+
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            let Some(deps) = doc.get_mut(section).and_then(|d| d.as_table_mut()) else {
+                continue;
+            };
+
+            let keys: Vec<String> = deps.iter().map(|(k, _)| k.to_string()).collect();
+            for key in keys {
+                if !collected.contains_key(&key) {
+                    continue;
+                }
+                let Some(item) = deps.get(&key) else {
+                    continue;
+                };
+                if is_workspace_dependency(item) || has_external_source(item) {
+                    continue;
+                }
+
+                let mut inline = InlineTable::new();
+                inline.insert("workspace", Value::from(true));
+                for field in ["features", "optional", "default-features"] {
+                    if let Some(value) = dependency_field_value(item, field) {
+                        inline.insert(field, value);
+                    }
+                }
+
+                deps.insert(&key, Item::Value(Value::InlineTable(inline)));
+                member_changed = true;
+                changes += 1;
+            }
+        }
+
+        if member_changed && !(args.dry_run || args.check) {
+            std::fs::write(&manifest_path, doc.to_string())
+                .context(format!("Failed to write {:?}", manifest_path))?;
+        }
+    }
+
+    if changes > 0 && !(args.dry_run || args.check) {
+        std::fs::write(&root_manifest_path, root_doc.to_string())
+            .context(format!("Failed to write {:?}", root_manifest_path))?;
+    }
+
+    if changes > 0 {
+        logger.println(&format!("\n📦 {}", root_manifest_path.display()));
+        logger.println(&format!(
+            "   ✓ Made {} change{} hoisting into [workspace.dependencies]",
+            changes,
+            if changes == 1 { "" } else { "s" }
+        ));
+    }
+
+    Ok(changes)
+}
+
+/// Returns `true` if a dependency entry already reads `{ workspace = true }`.
+fn is_workspace_dependency(item: &Item) -> bool {
+    match item {
+        Item::Value(Value::InlineTable(t)) => {
+            t.get("workspace").and_then(|v| v.as_bool()) == Some(true)
+        }
+        Item::Table(t) => {
+            t.get("workspace").and_then(|v| v.as_value()).and_then(|v| v.as_bool()) == Some(true)
+        }
+        _ => false,
+    }
+}
+
+/// Extracts the version requirement `Value` from a dependency entry, whether
+/// it is a bare string (`dep = "1.0"`) or an inline/full table
+/// (`dep = { version = "1.0", ... }`). Path/git dependencies without a
+/// `version` key return `None`.
+fn dependency_version_value(item: &Item) -> Option<Value> {
+    match item {
+        Item::Value(Value::String(s)) => Some(Value::String(s.clone())),
+        Item::Value(Value::InlineTable(t)) => t.get("version").cloned(),
+        Item::Table(t) => t.get("version").and_then(|v| v.as_value()).cloned(),
+        _ => None,
+    }
+}
+
+/// Extracts the raw version string from a version `Value`, ignoring
+/// formatting/decor, for equality comparisons.
+fn dependency_version_str(value: &Value) -> Option<String> {
+    value.as_str().map(|s| s.to_string())
+}
+
+/// Extracts a member-local field (e.g. `features`, `optional`,
+/// `default-features`) from a dependency entry so it can be preserved
+/// alongside `workspace = true`.
+fn dependency_field_value(item: &Item, field: &str) -> Option<Value> {
+    match item {
+        Item::Value(Value::InlineTable(t)) => t.get(field).cloned(),
+        Item::Table(t) => t.get(field).and_then(|v| v.as_value()).cloned(),
+        _ => None,
+    }
+}
+
+/// Returns `true` if a dependency entry carries `path`, `git`, or `registry`.
+/// A `path` is always relative to the member's own directory, not the
+/// workspace root, so it can't be copied verbatim into
+/// `[workspace.dependencies]` without being recomputed relative to the
+/// workspace root; `git`/`registry` dependencies are skipped for the same
+/// reason cargo doesn't let `workspace = true` members override them.
+fn has_external_source(item: &Item) -> bool {
+    ["path", "git", "registry"]
+        .iter()
+        .any(|field| dependency_field_value(item, field).is_some())
+}
+
+/// Inserts or updates a `[workspace.dependencies]` entry with the canonical
+/// version requirement, without clobbering an already-present entry's other
+/// keys (e.g. `features`, `default-features`) the way a bare overwrite would.
+/// Returns `true` if the entry was changed.
+fn upsert_workspace_dependency_version(ws_deps: &mut Table, name: &str, version: &Value) -> bool {
+    let current_version = ws_deps.get(name).and_then(dependency_version_value);
+    let current_version_str = current_version.as_ref().and_then(dependency_version_str);
+    let new_version_str = dependency_version_str(version);
+
+    if ws_deps.contains_key(name) && current_version_str == new_version_str {
+        return false;
+    }
+
+    match ws_deps.get_mut(name) {
+        Some(Item::Value(Value::InlineTable(t))) => {
+            t.insert("version", version.clone());
+        }
+        Some(Item::Table(t)) => {
+            t.insert("version", Item::Value(version.clone()));
+        }
+        _ => {
+            ws_deps.insert(name, Item::Value(version.clone()));
+        }
+    }
+
+    true
+}
+
+fn format_manifest(
+    manifest_path: &Path,
+    args: &FmtArgs,
+    config: &FmtConfig,
+    logger: &mut Logger,
+) -> Result<usize> {
     let content = std::fs::read_to_string(manifest_path)
         .context(format!("Failed to read {:?}", manifest_path))?;
 
@@ -222,30 +607,35 @@ fn format_manifest(manifest_path: &Path, args: &FmtArgs, logger: &mut Logger) ->
     changes += collapse_nested_tables(&mut doc, logger)?;
 
     // 2. Reorder sections in the document
-    changes += reorder_sections(&mut doc, logger)?;
+    changes += reorder_sections(&mut doc, &config.section_order, logger)?;
 
     // 3. Format [package] section
-    changes += format_package_section(&mut doc, logger)?;
+    changes += format_package_section(&mut doc, &config.package_key_order, logger)?;
 
     // 4. Sort all dependency sections
     changes += sort_dependencies(&mut doc, "dependencies", logger)?;
     changes += sort_dependencies(&mut doc, "dev-dependencies", logger)?;
     changes += sort_dependencies(&mut doc, "build-dependencies", logger)?;
 
-    // 5. Sort target-specific dependencies
+    // 5. Normalize dependency version requirements
+    changes += normalize_dependencies(&mut doc, args, logger)?;
+
+    // 6. Sort target-specific dependencies
     if let Some(target_table) = doc.get_mut("target").and_then(|t| t.as_table_mut()) {
         for (_target_name, target_config) in target_table.iter_mut() {
-            if target_config.get("dependencies").is_some()
-                && let Some(deps_table) = target_config
-                    .get_mut("dependencies")
-                    .and_then(|d| d.as_table_mut())
-            {
-                let collapsed = collapse_table_entries(deps_table);
-                if collapsed > 0 {
-                    deps_table.set_implicit(false);
-                    changes += collapsed;
+            for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+                if target_config.get(section).is_some()
+                    && let Some(deps_table) = target_config
+                        .get_mut(section)
+                        .and_then(|d| d.as_table_mut())
+                {
+                    let collapsed = collapse_table_entries(deps_table);
+                    if collapsed > 0 {
+                        deps_table.set_implicit(false);
+                        changes += collapsed;
+                    }
+                    changes += sort_table_in_place(deps_table, logger)?;
                 }
-                changes += sort_table_in_place(deps_table, logger)?;
             }
         }
     }
@@ -287,14 +677,16 @@ fn collapse_nested_tables(doc: &mut DocumentMut, logger: &mut Logger) -> Result<
 
     if let Some(target_table) = doc.get_mut("target").and_then(|t| t.as_table_mut()) {
         for (_target_name, target_config) in target_table.iter_mut() {
-            if let Some(deps_table) = target_config
-                .get_mut("dependencies")
-                .and_then(|d| d.as_table_mut())
-            {
-                let collapsed = collapse_table_entries(deps_table);
-                if collapsed > 0 {
-                    deps_table.set_implicit(false);
-                    changes += collapsed;
+            for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+                if let Some(deps_table) = target_config
+                    .get_mut(section)
+                    .and_then(|d| d.as_table_mut())
+                {
+                    let collapsed = collapse_table_entries(deps_table);
+                    if collapsed > 0 {
+                        deps_table.set_implicit(false);
+                        changes += collapsed;
+                    }
                 }
             }
         }
@@ -351,131 +743,76 @@ fn collapse_table_entries(table: &mut Table) -> usize {
     changes
 }
 
-fn reorder_sections(doc: &mut DocumentMut, logger: &mut Logger) -> Result<usize> {
-    // Define the desired section order
-    let section_order = vec![
-        "package",
-        "lib",
-        "bin",
-        "test",
-        "bench",
-        "example",
-        "dependencies",
-        "dev-dependencies",
-        "build-dependencies",
-        "target",
-        "features",
-    ];
+fn reorder_sections(
+    doc: &mut DocumentMut,
+    section_order: &[String],
+    logger: &mut Logger,
+) -> Result<usize> {
+    let section_rank = |key: &str| -> usize {
+        section_order
+            .iter()
+            .position(|s| s == key)
+            .unwrap_or(section_order.len())
+    };
 
     // Get current top-level keys
     let current_keys: Vec<String> = doc.iter().map(|(k, _)| k.to_string()).collect();
 
-    // Build expected order: ordered sections first, then any extra sections
-    let mut expected_keys = Vec::new();
-    for section in &section_order {
-        if current_keys.contains(&section.to_string()) {
-            expected_keys.push(section.to_string());
-        }
-    }
-
-    // Add any keys not in section_order at the end
-    for key in &current_keys {
-        if !section_order.contains(&key.as_str()) {
-            expected_keys.push(key.clone());
-        }
-    }
+    // Build expected order: ordered sections first (in `section_order`), then
+    // any extra sections in their original relative order
+    let mut expected_keys = current_keys.clone();
+    expected_keys.sort_by_key(|key| section_rank(key));
 
     // Check if reordering is needed
     if current_keys == expected_keys {
         return Ok(0);
     }
 
-    // Manually reconstruct the document string in the desired order
-    // This preserves all formatting including inline tables
-    let original_str = doc.to_string();
-    let mut section_strings: std::collections::HashMap<String, String> =
-        std::collections::HashMap::new();
-
-    // Split the document into sections manually by finding section headers
-    let mut current_section = String::new();
-    let mut current_section_name = String::new();
-
-    for line in original_str.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with('[') && !trimmed.starts_with("[[") {
-            // This is a new section header (not array-of-tables)
-            if !current_section_name.is_empty() {
-                section_strings.insert(current_section_name.clone(), current_section.clone());
-            }
-            // Extract section name
-            if let Some(end_bracket) = trimmed.find(']') {
-                current_section_name = trimmed[1..end_bracket].to_string();
-                current_section = format!("{}\n", line);
-            }
-        } else if trimmed.starts_with("[[") {
-            // Array-of-tables - treat specially (could be [[bin]], [[test]], etc.)
-            if !current_section_name.is_empty() {
-                section_strings.insert(current_section_name.clone(), current_section.clone());
-                current_section_name.clear();
-            }
-            // Extract array-of-tables section name
-            if let Some(end_bracket) = trimmed.find("]]") {
-                let section_name = trimmed[2..end_bracket].to_string();
-                current_section = format!("{}\n", line);
-                current_section_name = section_name;
-            }
-        } else {
-            current_section.push_str(line);
-            current_section.push('\n');
-        }
-    }
-
-    // Don't forget the last section
-    if !current_section_name.is_empty() {
-        section_strings.insert(current_section_name, current_section);
-    }
-
-    // Rebuild in the desired order
-    let mut new_content = String::new();
-    for key in &expected_keys {
-        if let Some(section_str) = section_strings.get(key) {
-            if !new_content.is_empty() && !new_content.ends_with("\n\n") {
-                new_content.push('\n');
+    // `DocumentMut` serializes top-level tables (and array-of-tables, e.g.
+    // `[[bin]]`) in order of each `Table`'s own `doc_position`, not the
+    // order of entries in the parent map — so reordering the map alone
+    // (e.g. via `Table::sort_values_by`) has no effect on the written file.
+    // Assign each section a fresh position, spaced out so every entry in an
+    // array-of-tables can keep its own slot while staying within its
+    // section's range and in its original relative order.
+    const SECTION_GAP: usize = 1_000;
+    for (index, key) in expected_keys.iter().enumerate() {
+        let base_position = index * SECTION_GAP;
+        if let Some(item) = doc.get_mut(key) {
+            match item {
+                Item::Table(table) => table.set_position(base_position),
+                Item::ArrayOfTables(array) => {
+                    for (offset, table) in array.iter_mut().enumerate() {
+                        table.set_position(base_position + offset);
+                    }
+                }
+                _ => {}
             }
-            new_content.push_str(section_str);
         }
     }
 
-    // Parse the reordered content back
-    *doc = new_content
-        .parse::<DocumentMut>()
-        .context("Failed to parse reordered document")?;
+    // Also reorder the logical map so that any section without an explicit
+    // position (e.g. newly inserted) falls back to the same order.
+    doc.as_table_mut()
+        .sort_values_by(|key1, _, key2, _| section_rank(key1.get()).cmp(&section_rank(key2.get())));
 
     logger.println("   ✓ Reordered sections");
 
     Ok(1)
 }
 
-fn format_package_section(doc: &mut DocumentMut, logger: &mut Logger) -> Result<usize> {
+fn format_package_section(
+    doc: &mut DocumentMut,
+    desired_order: &[String],
+    logger: &mut Logger,
+) -> Result<usize> {
     let mut changes = 0;
 
     if let Some(package) = doc.get_mut("package").and_then(|p| p.as_table_mut()) {
-        // Define the desired order
-        let desired_order = vec![
-            "name",
-            "description",
-            "version",
-            "edition",
-            "license-file",
-            "authors",
-            "rust-version",
-            "readme",
-        ];
-
         // Check if order is correct
         let current_keys: Vec<String> = package.iter().map(|(k, _)| k.to_string()).collect();
         let mut expected_keys = Vec::new();
-        for key in &desired_order {
+        for key in desired_order {
             if package.contains_key(key) {
                 expected_keys.push(key.to_string());
             }
@@ -483,7 +820,7 @@ fn format_package_section(doc: &mut DocumentMut, logger: &mut Logger) -> Result<
 
         // Add any keys that aren't in desired_order at the end
         for key in &current_keys {
-            if !desired_order.contains(&key.as_str()) {
+            if !desired_order.iter().any(|k| k == key) {
                 expected_keys.push(key.clone());
             }
         }
@@ -548,3 +885,464 @@ fn sort_table_in_place(table: &mut Table, logger: &mut Logger) -> Result<usize>
 
     Ok(0)
 }
+
+/// Normalizes version requirements across `dependencies`, `dev-dependencies`,
+/// `build-dependencies`, and their `target.*`-scoped counterparts, per
+/// `FmtArgs::keep_full_version`.
+fn normalize_dependencies(doc: &mut DocumentMut, args: &FmtArgs, logger: &mut Logger) -> Result<usize> {
+    let mut changes = 0;
+
+    for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        if let Some(deps) = doc.get_mut(section).and_then(|d| d.as_table_mut()) {
+            changes += normalize_dependency_table(deps, args.keep_full_version);
+        }
+    }
+
+    if let Some(target_table) = doc.get_mut("target").and_then(|t| t.as_table_mut()) {
+        for (_target_name, target_config) in target_table.iter_mut() {
+            for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+                if let Some(deps_table) = target_config
+                    .get_mut(section)
+                    .and_then(|d| d.as_table_mut())
+                {
+                    changes += normalize_dependency_table(deps_table, args.keep_full_version);
+                }
+            }
+        }
+    }
+
+    if changes > 0 {
+        logger.println("   ✓ Normalized dependency version requirements");
+    }
+
+    Ok(changes)
+}
+
+/// Normalizes the version requirement of every entry in a dependency table,
+/// whether written as a bare string (`dep = "1.0.0"`) or an inline/full table
+/// (`dep = { version = "1.0.0", ... }`).
+fn normalize_dependency_table(table: &mut Table, keep_full_version: bool) -> usize {
+    let mut changes = 0;
+    let keys: Vec<String> = table.iter().map(|(k, _)| k.to_string()).collect();
+
+    for key in keys {
+        let Some(item) = table.get_mut(&key) else {
+            continue;
+        };
+
+        match item {
+            Item::Value(Value::String(s)) => {
+                let normalized = normalize_version_requirement(s.value(), keep_full_version);
+                if normalized != *s.value() {
+                    let mut new_value = Value::from(normalized);
+                    *new_value.decor_mut() = s.decor().clone();
+                    *item = Item::Value(new_value);
+                    changes += 1;
+                }
+            }
+            Item::Value(Value::InlineTable(t)) => {
+                changes += normalize_version_field(t, keep_full_version) as usize;
+            }
+            Item::Table(inner) => {
+                changes += normalize_version_field(inner, keep_full_version) as usize;
+            }
+            _ => {}
+        }
+    }
+
+    changes
+}
+
+/// Normalizes the `version` key of a dependency table-like item (inline or
+/// full table), returning `true` if it was rewritten.
+fn normalize_version_field(table: &mut impl TableLike, keep_full_version: bool) -> bool {
+    let Some(version) = table.get("version").and_then(|v| v.as_value()).and_then(|v| v.as_str())
+    else {
+        return false;
+    };
+
+    let normalized = normalize_version_requirement(version, keep_full_version);
+    if normalized == version {
+        return false;
+    }
+
+    let existing_decor = table.get("version").and_then(|v| v.as_value()).map(|v| v.decor().clone());
+    let mut new_value = Value::from(normalized);
+    if let Some(decor) = existing_decor {
+        *new_value.decor_mut() = decor;
+    }
+    table.insert("version", Item::Value(new_value));
+
+    true
+}
+
+/// Canonicalizes a (possibly comma-separated) version requirement string.
+/// When `keep_full_version` is `false`, trims redundant trailing `.0`
+/// components from each comparator (`1.0.0` -> `1`, `2.3.0` -> `2.3`) unless
+/// a pre-release/build-metadata suffix is present.
+fn normalize_version_requirement(requirement: &str, keep_full_version: bool) -> String {
+    if keep_full_version {
+        return requirement.to_string();
+    }
+
+    requirement
+        .split(',')
+        .map(|comparator| normalize_version_comparator(comparator.trim()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Normalizes a single comparator (e.g. `>=1.2.0` or `1.0.0`), preserving any
+/// leading operator.
+fn normalize_version_comparator(comparator: &str) -> String {
+    let operator_len = comparator
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(comparator.len());
+    let (operator, version) = comparator.split_at(operator_len);
+    // Trailing zeros are only redundant for caret (bare or `^`), `>=` and `<`
+    // comparators; e.g. `=1.0.0` -> `=1` or `~1.0.0` -> `~1` would widen them.
+    if !matches!(operator.trim(), "" | "^" | ">=" | "<") {
+        return comparator.to_string();
+    }
+    format!("{operator}{}", trim_trailing_zero_components(version))
+}
+
+/// Trims redundant trailing `.0` version components. Versions carrying a
+/// pre-release or build-metadata suffix (e.g. `1.0.0-alpha`) are left
+/// entirely untouched, since trimming their numeric core would change their
+/// meaning. Versions whose major and minor are both `0` (e.g. `0.0.0`,
+/// `0.0`) are left untouched too: under Cargo's caret semantics `0.0.x`
+/// requirements each cover a different, narrower range (`0.0.0` permits only
+/// `0.0.0`, `0.0` permits the whole `0.0.x` series, `0` permits all of
+/// `0.x.y`), so trimming any of their trailing zeros would widen what the
+/// requirement accepts.
+fn trim_trailing_zero_components(version: &str) -> String {
+    if version.contains(['-', '+']) {
+        return version.to_string();
+    }
+
+    let mut components: Vec<&str> = version.split('.').collect();
+    if components.first() == Some(&"0") && components.get(1) == Some(&"0") {
+        return version.to_string();
+    }
+
+    while components.len() > 1 && components.last() == Some(&"0") {
+        components.pop();
+    }
+
+    components.join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a fresh temp directory under `name`, writing each
+    /// `(relative_path, contents)` pair into it. Used by tests that need real
+    /// files on disk (e.g. `hoist_workspace_dependencies`,
+    /// `discover_crate_manifests`).
+    fn write_temp_workspace(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cargo-fmt-toml-test-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (rel_path, contents) in files {
+            let path = dir.join(rel_path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn hoist_workspace_dependencies_tolerates_equivalent_version_strings() {
+        let workspace = write_temp_workspace(
+            "hoist-conflict",
+            &[
+                ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n"),
+                (
+                    "crates/foo/Cargo.toml",
+                    "[package]\nname = \"foo\"\n\n[dependencies]\nanyhow = \"1.0.0\"\n",
+                ),
+                (
+                    "crates/bar/Cargo.toml",
+                    "[package]\nname = \"bar\"\n\n[dependencies]\nanyhow = \"1.0\"\n",
+                ),
+            ],
+        );
+
+        let args = FmtArgs {
+            dry_run: false,
+            check: false,
+            workspace_path: workspace.clone(),
+            quiet: true,
+            keep_full_version: false,
+        };
+        let crate_manifests = vec![
+            workspace.join("crates/foo/Cargo.toml"),
+            workspace.join("crates/bar/Cargo.toml"),
+        ];
+        let mut logger = Logger::new(true);
+
+        let changes = hoist_workspace_dependencies(&args, &crate_manifests, &mut logger).unwrap();
+        assert!(changes > 0, "equivalent version strings should hoist, not conflict");
+
+        let root = std::fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+        assert!(root.contains("[workspace.dependencies]"));
+        assert!(root.contains("anyhow"));
+
+        std::fs::remove_dir_all(&workspace).ok();
+    }
+
+    #[test]
+    fn hoist_workspace_dependencies_sorts_and_normalizes_workspace_dependencies() {
+        let workspace = write_temp_workspace(
+            "hoist-sort-normalize",
+            &[
+                (
+                    "Cargo.toml",
+                    "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.dependencies]\nzeta = \"1.0.0\"\n",
+                ),
+                (
+                    "crates/foo/Cargo.toml",
+                    "[package]\nname = \"foo\"\n\n[dependencies]\nanyhow = \"1.0.0\"\n",
+                ),
+            ],
+        );
+
+        let args = FmtArgs {
+            dry_run: false,
+            check: false,
+            workspace_path: workspace.clone(),
+            quiet: true,
+            keep_full_version: false,
+        };
+        let crate_manifests = vec![workspace.join("crates/foo/Cargo.toml")];
+        let mut logger = Logger::new(true);
+
+        let changes = hoist_workspace_dependencies(&args, &crate_manifests, &mut logger).unwrap();
+        assert!(changes > 0);
+
+        let root = std::fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+        assert!(root.contains("anyhow = \"1\""), "newly hoisted anyhow should be trimmed");
+        assert!(root.contains("zeta = \"1\""), "pre-existing zeta should also be trimmed");
+        let anyhow_pos = root.find("anyhow").unwrap();
+        let zeta_pos = root.find("zeta").unwrap();
+        assert!(anyhow_pos < zeta_pos, "workspace.dependencies should be sorted alphabetically");
+
+        std::fs::remove_dir_all(&workspace).ok();
+    }
+
+    #[test]
+    fn reorder_sections_preserves_comments_and_bin_order() {
+        let input = r#"
+# workspace metadata
+[dependencies]
+anyhow = "1"
+
+# package comment
+[package]
+name = "demo"
+version = "0.1.0"
+
+[[bin]]
+name = "a"
+path = "src/a.rs"
+
+[[bin]]
+name = "b"
+path = "src/b.rs"
+"#;
+        let mut doc = input.parse::<DocumentMut>().unwrap();
+        let mut logger = Logger::new(true);
+        let section_order = FmtConfig::default().section_order;
+
+        let changes = reorder_sections(&mut doc, &section_order, &mut logger).unwrap();
+        assert_eq!(changes, 1);
+
+        let output = doc.to_string();
+        let package_pos = output.find("[package]").unwrap();
+        let deps_pos = output.find("[dependencies]").unwrap();
+        assert!(package_pos < deps_pos, "package should now come before dependencies");
+        assert!(output.contains("# workspace metadata\n[dependencies]"));
+        assert!(output.contains("# package comment\n[package]"));
+
+        let bin_a = output.find("name = \"a\"").unwrap();
+        let bin_b = output.find("name = \"b\"").unwrap();
+        assert!(bin_a < bin_b, "[[bin]] entries should keep their original relative order");
+        assert_eq!(output.matches("[[bin]]").count(), 2);
+
+        // Running again should be a no-op.
+        let changes_again = reorder_sections(&mut doc, &section_order, &mut logger).unwrap();
+        assert_eq!(changes_again, 0);
+    }
+
+    #[test]
+    fn target_dev_and_build_dependencies_are_collapsed_and_sorted() {
+        let input = r#"
+[target.'cfg(unix)'.dev-dependencies.zeta]
+version = "1"
+
+[target.'cfg(unix)'.dev-dependencies.alpha]
+version = "1"
+"#;
+        let mut doc = input.parse::<DocumentMut>().unwrap();
+        let mut logger = Logger::new(true);
+
+        let target_table = doc.get_mut("target").unwrap().as_table_mut().unwrap();
+        let target_config = target_table.iter_mut().next().unwrap().1;
+        let deps_table = target_config
+            .get_mut("dev-dependencies")
+            .unwrap()
+            .as_table_mut()
+            .unwrap();
+
+        let collapsed = collapse_table_entries(deps_table);
+        assert_eq!(collapsed, 2);
+        deps_table.set_implicit(false);
+
+        let sorted = sort_table_in_place(deps_table, &mut logger).unwrap();
+        assert_eq!(sorted, 1);
+
+        let output = doc.to_string();
+        assert!(output.contains("[target.'cfg(unix)'.dev-dependencies]"));
+        assert!(!output.contains("[target.'cfg(unix)'.dev-dependencies.zeta]"));
+        let alpha_pos = output.find("alpha").unwrap();
+        let zeta_pos = output.find("zeta").unwrap();
+        assert!(alpha_pos < zeta_pos, "dependencies should be sorted alphabetically");
+    }
+
+    #[test]
+    fn upsert_workspace_dependency_version_merges_instead_of_clobbering() {
+        let mut doc = "[workspace.dependencies]\ntokio = { version = \"1\", features = [\"full\"] }\n"
+            .parse::<DocumentMut>()
+            .unwrap();
+        let ws_deps = doc["workspace"]["dependencies"].as_table_mut().unwrap();
+
+        let changed = upsert_workspace_dependency_version(ws_deps, "tokio", &Value::from("2"));
+        assert!(changed);
+
+        let output = doc.to_string();
+        assert!(output.contains("features = [\"full\"]"));
+        assert!(output.contains("version = \"2\""));
+    }
+
+    #[test]
+    fn has_external_source_detects_path_git_and_registry() {
+        let doc = "[dependencies]\n\
+                    a = { path = \"../a\", version = \"1\" }\n\
+                    b = { git = \"https://example.com/b\" }\n\
+                    c = { registry = \"my-registry\", version = \"1\" }\n\
+                    d = \"1\"\n\
+                    e = { version = \"1\", features = [\"x\"] }\n"
+            .parse::<DocumentMut>()
+            .unwrap();
+        let deps = doc["dependencies"].as_table().unwrap();
+
+        for name in ["a", "b", "c"] {
+            assert!(
+                has_external_source(deps.get(name).unwrap()),
+                "{name} should be detected as having an external source"
+            );
+        }
+        for name in ["d", "e"] {
+            assert!(
+                !has_external_source(deps.get(name).unwrap()),
+                "{name} should not be detected as having an external source"
+            );
+        }
+    }
+
+    #[test]
+    fn trim_trailing_zero_components_leaves_zero_zero_versions_untouched() {
+        assert_eq!(trim_trailing_zero_components("1.0.0"), "1");
+        assert_eq!(trim_trailing_zero_components("0.1.0"), "0.1");
+        assert_eq!(trim_trailing_zero_components("0.0.0"), "0.0.0");
+        assert_eq!(trim_trailing_zero_components("0.0"), "0.0");
+        assert_eq!(trim_trailing_zero_components("0.0.1"), "0.0.1");
+        assert_eq!(trim_trailing_zero_components("1.0.0-alpha"), "1.0.0-alpha");
+    }
+
+    #[test]
+    fn discover_crate_manifests_finds_immediate_children_with_star_form() {
+        let workspace = write_temp_workspace(
+            "discover-star",
+            &[
+                ("crates/foo/Cargo.toml", "[package]\nname = \"foo\"\n"),
+                ("crates/bar/Cargo.toml", "[package]\nname = \"bar\"\n"),
+                // Nested two levels deep: should NOT be picked up by `dir/*`.
+                ("crates/foo/nested/Cargo.toml", "[package]\nname = \"nested\"\n"),
+            ],
+        );
+
+        let manifests = discover_crate_manifests(&workspace, &["crates/*".to_string()]);
+
+        assert_eq!(
+            manifests,
+            vec![workspace.join("crates/bar/Cargo.toml"), workspace.join("crates/foo/Cargo.toml")]
+        );
+
+        std::fs::remove_dir_all(&workspace).ok();
+    }
+
+    #[test]
+    fn discover_crate_manifests_finds_any_depth_with_double_star_form() {
+        let workspace = write_temp_workspace(
+            "discover-double-star",
+            &[
+                ("pkgs/a/Cargo.toml", "[package]\nname = \"a\"\n"),
+                ("pkgs/sub/b/Cargo.toml", "[package]\nname = \"b\"\n"),
+            ],
+        );
+
+        let manifests = discover_crate_manifests(&workspace, &["pkgs/**".to_string()]);
+
+        assert_eq!(
+            manifests,
+            vec![workspace.join("pkgs/a/Cargo.toml"), workspace.join("pkgs/sub/b/Cargo.toml")]
+        );
+
+        std::fs::remove_dir_all(&workspace).ok();
+    }
+
+    #[test]
+    fn discover_crate_manifests_finds_single_manifest_with_bare_form() {
+        let workspace = write_temp_workspace("discover-bare", &[("lib/Cargo.toml", "[package]\nname = \"lib\"\n")]);
+
+        let manifests = discover_crate_manifests(&workspace, &["lib".to_string()]);
+
+        assert_eq!(manifests, vec![workspace.join("lib/Cargo.toml")]);
+
+        std::fs::remove_dir_all(&workspace).ok();
+    }
+
+    #[test]
+    fn fmt_config_load_falls_back_to_defaults_for_unset_keys() {
+        let workspace = write_temp_workspace(
+            "fmt-config-partial",
+            &[("fmt-toml.toml", "section_order = [\"package\", \"dependencies\"]\n")],
+        );
+
+        let config = FmtConfig::load(&workspace).unwrap();
+        let defaults = FmtConfig::default();
+
+        assert_eq!(config.section_order, vec!["package".to_string(), "dependencies".to_string()]);
+        assert_eq!(config.package_key_order, defaults.package_key_order);
+        assert_eq!(config.scan_roots, defaults.scan_roots);
+
+        std::fs::remove_dir_all(&workspace).ok();
+    }
+
+    #[test]
+    fn fmt_config_load_falls_back_to_defaults_when_file_absent() {
+        let workspace = write_temp_workspace("fmt-config-absent", &[]);
+
+        let config = FmtConfig::load(&workspace).unwrap();
+        let defaults = FmtConfig::default();
+
+        assert_eq!(config.section_order, defaults.section_order);
+        assert_eq!(config.package_key_order, defaults.package_key_order);
+        assert_eq!(config.scan_roots, defaults.scan_roots);
+
+        std::fs::remove_dir_all(&workspace).ok();
+    }
+}